@@ -13,15 +13,17 @@ use bevy::{
         InputSystem,
         gamepad::{gamepad_connection_system, gamepad_event_processing_system},
     },
-    math::{
-        bounding::{Aabb2d, BoundingCircle, BoundingVolume, IntersectsVolume},
-        ops,
-    },
+    math::ops,
     prelude::*,
     state::app::StatesPlugin,
     time::TimePlugin,
 };
-use bevy_mod_gba::{AgbSoundPlugin, Sprite, SpriteHandles, Video, prelude::*};
+use bevy_mod_gba::{
+    AgbSoundPlugin, Sprite, SpriteHandles, Video,
+    audio::{AudioSource, PlaybackSettings, SpatialAudioSource, SpatialListener},
+    collision::{collide, resolve},
+    prelude::*,
+};
 
 // These constants are defined in `Transform` units.
 // Using the default 2D camera they correspond 1:1 with screen pixels.
@@ -153,8 +155,11 @@ struct Ball;
 #[derive(Component, Deref, DerefMut)]
 struct Velocity(Vec2);
 
-#[derive(Event, Default)]
-struct CollisionEvent;
+#[derive(Event)]
+struct CollisionEvent {
+    /// Where the collision happened, used to position the collision sound's emitter.
+    position: Vec2,
+}
 
 #[derive(Component)]
 struct Brick;
@@ -256,6 +261,7 @@ fn setup(mut commands: Commands, sprites: NonSend<Option<Sprites>>) {
         Collider {
             half_size: PADDLE_SIZE / 2.,
         },
+        SpatialListener,
     ));
 
     // Ball
@@ -361,31 +367,32 @@ fn apply_velocity(mut query: Query<(&mut Transform, &Velocity)>, time: Res<Time>
 fn check_for_collisions(
     mut commands: Commands,
     mut score: ResMut<Score>,
-    ball_query: Single<(&mut Velocity, &Transform), With<Ball>>,
+    ball_query: Single<(&mut Transform, &mut Velocity), With<Ball>>,
     collider_query: Query<(Entity, &Transform, Option<&Brick>, &Collider)>,
     mut collision_events: EventWriter<CollisionEvent>,
 ) {
-    let (mut ball_velocity, ball_transform) = ball_query.into_inner();
+    let (mut ball_transform, mut ball_velocity) = ball_query.into_inner();
+
+    // The ball's translation (like every sprite's) is its top-left corner, so the collider
+    // needs to be re-centered before it can be compared against another box.
+    let ball_half_size = Vec2::splat(BALL_DIAMETER / 2.);
+    let mut ball_center = ball_transform.translation.truncate() + ball_half_size;
 
     for (collider_entity, collider_transform, maybe_brick, collider) in &collider_query {
-        let collision = ball_collision(
-            BoundingCircle::new(
-                ball_transform.translation.truncate()
-                    + Vec2 {
-                        x: BALL_DIAMETER / 2.,
-                        y: BALL_DIAMETER / 2.,
-                    },
-                BALL_DIAMETER / 2.,
-            ),
-            Aabb2d::new(
-                collider_transform.translation.truncate() + collider.half_size,
-                collider.half_size,
-            ),
+        let collider_center = collider_transform.translation.truncate() + collider.half_size;
+
+        let collision = collide(
+            ball_center,
+            ball_half_size * 2.,
+            collider_center,
+            collider.half_size * 2.,
         );
 
         if let Some(collision) = collision {
             // Writes a collision event so that other systems can react to the collision
-            collision_events.write_default();
+            collision_events.write(CollisionEvent {
+                position: ball_center,
+            });
 
             // Bricks should be despawned and increment the scoreboard on collision
             if maybe_brick.is_some() {
@@ -395,74 +402,25 @@ fn check_for_collisions(
                 ball_velocity.0 += Vec2::splat(SCORE_SPEED_INCREMENT);
             }
 
-            // Reflect the ball's velocity when it collides
-            let mut reflect_x = false;
-            let mut reflect_y = false;
-
-            // Reflect only if the velocity is in the opposite direction of the collision
-            // This prevents the ball from getting stuck inside the bar
-            match collision {
-                Collision::Left => reflect_x = ball_velocity.x > 0.0,
-                Collision::Right => reflect_x = ball_velocity.x < 0.0,
-                Collision::Top => reflect_y = ball_velocity.y > 0.0,
-                Collision::Bottom => reflect_y = ball_velocity.y < 0.0,
-            }
-
-            // Reflect velocity on the x-axis if we hit something on the x-axis
-            if reflect_x {
-                ball_velocity.x = -ball_velocity.x;
-            }
-
-            // Reflect velocity on the y-axis if we hit something on the y-axis
-            if reflect_y {
-                ball_velocity.y = -ball_velocity.y;
-            }
+            // Push the ball out of the collider before reflecting, so it can't get stuck
+            // inside the paddle or a brick
+            resolve(&mut ball_center, &mut ball_velocity, collision);
         }
     }
+
+    ball_transform.translation = (ball_center - ball_half_size).extend(ball_transform.translation.z);
 }
 
-fn play_collision_sound(
-    mut collision_events: EventReader<CollisionEvent>,
-    mut mixer: NonSendMut<agb::sound::mixer::Mixer>,
-) {
+fn play_collision_sound(mut commands: Commands, mut collision_events: EventReader<CollisionEvent>) {
     static COLLISION_SOUND: &[u8] = agb::include_wav!("assets/sounds/breakout_collision.wav");
 
-    if !collision_events.is_empty() {
-        let sound_channel = agb::sound::mixer::SoundChannel::new(COLLISION_SOUND);
-        mixer.play_sound(sound_channel);
+    for event in collision_events.read() {
+        commands.spawn((
+            AudioSource::new(COLLISION_SOUND),
+            PlaybackSettings::ONCE,
+            SpatialAudioSource::default(),
+            Transform::from_translation(event.position.extend(0.0)),
+        ));
     }
-
-    collision_events.clear();
-}
-
-#[derive(Debug, PartialEq, Eq, Copy, Clone)]
-enum Collision {
-    Left,
-    Right,
-    Top,
-    Bottom,
 }
 
-// Returns `Some` if `ball` collides with `bounding_box`.
-// The returned `Collision` is the side of `bounding_box` that `ball` hit.
-fn ball_collision(ball: BoundingCircle, bounding_box: Aabb2d) -> Option<Collision> {
-    if !ball.intersects(&bounding_box) {
-        return None;
-    }
-
-    let closest = bounding_box.closest_point(ball.center());
-    let offset = ball.center() - closest;
-    let side = if offset.x.abs() > offset.y.abs() {
-        if offset.x < 0. {
-            Collision::Left
-        } else {
-            Collision::Right
-        }
-    } else if offset.y < 0. {
-        Collision::Top
-    } else {
-        Collision::Bottom
-    };
-
-    Some(side)
-}
@@ -1,7 +1,16 @@
 use core::ops::{Deref, DerefMut};
 
+use agb::sound::mixer::SoundChannel;
 use bevy::prelude::*;
 
+mod sink;
+mod source;
+mod spatial;
+
+pub use sink::AudioSink;
+pub use source::{AudioSource, PlaybackSettings};
+pub use spatial::{SpatialAudioSource, SpatialListener};
+
 /// Sets up the sound sub-system.
 #[derive(Default, Clone, Copy, PartialEq, Eq, Debug)]
 pub struct AgbSoundPlugin {
@@ -15,6 +24,16 @@ pub struct AgbSoundPlugin {
 
 impl Plugin for AgbSoundPlugin {
     fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (
+                play_audio_sources,
+                spatial::update_spatial_audio,
+                despawn_finished_audio,
+            )
+                .chain(),
+        );
+
         app.add_systems(
             PostUpdate,
             |mixer: Option<NonSendMut<agb::sound::mixer::Mixer>>| {
@@ -122,3 +141,46 @@ impl Channel<2> {
         }
     }
 }
+
+/// Starts a mixer channel for every entity that has an [`AudioSource`] and [`PlaybackSettings`]
+/// but no [`AudioSink`] yet, then stashes the resulting [`ChannelId`](agb::sound::mixer::ChannelId)
+/// on the entity so it can be paused, stopped, or queried later.
+fn play_audio_sources(
+    mut commands: Commands,
+    mixer: Option<NonSendMut<agb::sound::mixer::Mixer>>,
+    sources: Query<(Entity, &AudioSource, &PlaybackSettings), Without<AudioSink>>,
+) {
+    let Some(mut mixer) = mixer else {
+        return;
+    };
+
+    for (entity, source, settings) in &sources {
+        let mut channel = SoundChannel::new(source.0);
+
+        channel
+            .volume(settings.volume)
+            .playback_speed(settings.speed)
+            .should_loop(settings.looping);
+
+        let channel = mixer.play_sound(channel);
+
+        commands.entity(entity).insert(AudioSink { channel });
+    }
+}
+
+/// Despawns entities whose [`AudioSink`] has finished and whose [`PlaybackSettings`] asked for it.
+fn despawn_finished_audio(
+    mut commands: Commands,
+    mixer: Option<NonSend<agb::sound::mixer::Mixer>>,
+    sinks: Query<(Entity, &AudioSink, &PlaybackSettings)>,
+) {
+    let Some(mixer) = mixer else {
+        return;
+    };
+
+    for (entity, sink, settings) in &sinks {
+        if settings.despawn_on_finish && !settings.looping && sink.is_finished(&mixer) {
+            commands.entity(entity).despawn();
+        }
+    }
+}
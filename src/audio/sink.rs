@@ -0,0 +1,45 @@
+use agb::sound::mixer::{ChannelId, Mixer};
+use bevy::prelude::*;
+
+/// A handle to a sound actively playing through the GBA's mixer.
+///
+/// Inserted onto an entity by [`AgbSoundPlugin`](super::AgbSoundPlugin) once its
+/// [`AudioSource`](super::AudioSource) has been handed off to the [`Mixer`]. Use it to
+/// pause, resume, or stop the sound from any system that has access to the [`Mixer`].
+#[derive(Component)]
+pub struct AudioSink {
+    pub(super) channel: ChannelId,
+}
+
+impl AudioSink {
+    /// The mixer channel this sink is driving.
+    pub fn channel_id(&self) -> ChannelId {
+        self.channel
+    }
+
+    /// Pauses playback on this channel.
+    pub fn pause(&self, mixer: &mut Mixer) {
+        if let Some(channel) = mixer.channel(&self.channel) {
+            channel.pause();
+        }
+    }
+
+    /// Resumes a previously paused channel.
+    pub fn resume(&self, mixer: &mut Mixer) {
+        if let Some(channel) = mixer.channel(&self.channel) {
+            channel.resume();
+        }
+    }
+
+    /// Stops the channel immediately, regardless of its [`PlaybackSettings`](super::PlaybackSettings).
+    pub fn stop(&self, mixer: &mut Mixer) {
+        if let Some(channel) = mixer.channel(&self.channel) {
+            channel.stop();
+        }
+    }
+
+    /// Returns `true` once the channel has finished playing and been reclaimed by the mixer.
+    pub fn is_finished(&self, mixer: &Mixer) -> bool {
+        mixer.channel(&self.channel).is_none()
+    }
+}
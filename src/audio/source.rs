@@ -0,0 +1,78 @@
+use bevy::prelude::*;
+
+/// A sound effect loaded into ROM, ready to be handed off to the mixer.
+///
+/// Wrap the byte slice produced by [`agb::include_wav!`] to use it as an ECS component:
+///
+/// ```ignore
+/// static BLIP: &[u8] = agb::include_wav!("assets/sounds/blip.wav");
+/// commands.spawn((AudioSource::new(BLIP), PlaybackSettings::ONCE));
+/// ```
+#[derive(Component, Clone, Copy, Deref, DerefMut)]
+pub struct AudioSource(pub &'static [u8]);
+
+impl AudioSource {
+    /// Wraps a byte slice produced by [`agb::include_wav!`] as a playable [`AudioSource`].
+    pub const fn new(bytes: &'static [u8]) -> Self {
+        Self(bytes)
+    }
+}
+
+/// Configures how an [`AudioSource`] should be played once it gains an [`AudioSink`](super::AudioSink).
+#[derive(Component, Clone, Copy, Debug, PartialEq)]
+pub struct PlaybackSettings {
+    /// Playback volume, from `0.0` (silent) to `1.0` (full volume).
+    pub volume: f32,
+    /// Relative playback speed, where `1.0` is the source's native rate.
+    pub speed: f32,
+    /// If `true`, the channel restarts from the beginning every time it finishes.
+    pub looping: bool,
+    /// If `true`, the entity is despawned once the channel finishes playing.
+    ///
+    /// Has no effect while `looping` is `true`, since a looping channel never finishes.
+    pub despawn_on_finish: bool,
+}
+
+impl Default for PlaybackSettings {
+    fn default() -> Self {
+        Self::ONCE
+    }
+}
+
+impl PlaybackSettings {
+    /// Plays the sound once, then despawns the entity.
+    pub const ONCE: Self = Self {
+        volume: 1.0,
+        speed: 1.0,
+        looping: false,
+        despawn_on_finish: true,
+    };
+
+    /// Plays the sound once, leaving the entity (and its now-finished [`AudioSink`](super::AudioSink)) in place.
+    pub const PERSIST: Self = Self {
+        volume: 1.0,
+        speed: 1.0,
+        looping: false,
+        despawn_on_finish: false,
+    };
+
+    /// Loops the sound until the entity, its [`AudioSource`], or this component is removed.
+    pub const LOOP: Self = Self {
+        volume: 1.0,
+        speed: 1.0,
+        looping: true,
+        despawn_on_finish: false,
+    };
+
+    /// Sets the playback volume.
+    pub const fn with_volume(mut self, volume: f32) -> Self {
+        self.volume = volume;
+        self
+    }
+
+    /// Sets the playback speed.
+    pub const fn with_speed(mut self, speed: f32) -> Self {
+        self.speed = speed;
+        self
+    }
+}
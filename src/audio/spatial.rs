@@ -0,0 +1,49 @@
+use bevy::prelude::*;
+
+use super::{AudioSink, PlaybackSettings};
+
+/// Marks the entity emitters pan and attenuate relative to, such as the camera or player paddle.
+///
+/// Only one [`SpatialListener`] should exist at a time; if several are present, the emitter
+/// systems use an arbitrary one of them.
+#[derive(Component, Clone, Copy, Debug, Default)]
+pub struct SpatialListener;
+
+/// Pairs with an [`AudioSource`](super::AudioSource) to pan and attenuate a sound based on the
+/// horizontal offset between the emitter's [`Transform`] and the [`SpatialListener`]'s.
+#[derive(Component, Clone, Copy, Debug, PartialEq)]
+pub struct SpatialAudioSource {
+    /// The horizontal distance, in `Transform` units, at which the emitter is fully panned to
+    /// one side and has faded out completely.
+    pub range: f32,
+}
+
+impl Default for SpatialAudioSource {
+    fn default() -> Self {
+        Self { range: 160.0 }
+    }
+}
+
+/// Recomputes panning and volume for every playing [`SpatialAudioSource`], based on its distance
+/// from the [`SpatialListener`].
+pub(super) fn update_spatial_audio(
+    mixer: Option<NonSendMut<agb::sound::mixer::Mixer>>,
+    listeners: Query<&Transform, With<SpatialListener>>,
+    emitters: Query<(&Transform, &SpatialAudioSource, &PlaybackSettings, &AudioSink)>,
+) {
+    let (Some(mut mixer), Some(listener)) = (mixer, listeners.iter().next()) else {
+        return;
+    };
+
+    for (transform, source, settings, sink) in &emitters {
+        let Some(channel) = mixer.channel(&sink.channel_id()) else {
+            continue;
+        };
+
+        let offset = transform.translation.x - listener.translation.x;
+        let pan = (offset / source.range).clamp(-1.0, 1.0);
+        let attenuation = (1.0 - offset.abs() / source.range).clamp(0.0, 1.0);
+
+        channel.panning(pan).volume(settings.volume * attenuation);
+    }
+}
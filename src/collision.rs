@@ -0,0 +1,157 @@
+//! Reusable axis-aligned bounding box collision detection and penetration-depth resolution.
+//!
+//! This operates on `Vec2`/`f32` rather than a fixed-point type, matching the rest of the crate's
+//! `f32`-based `Transform`. On the GBA's ARM7TDMI (no hardware FPU) that means `f32` ops go
+//! through software emulation; this module doesn't attempt to avoid that cost, and a fixed-point
+//! rewrite would need to thread a different numeric type through `Transform` itself first.
+
+use bevy::prelude::*;
+
+/// Which side of the stationary box the moving box struck.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CollisionSide {
+    Left,
+    Right,
+    Top,
+    Bottom,
+}
+
+/// The result of an overlapping [`collide`] test.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Collision {
+    /// Which side of `b` was struck by `a`.
+    pub side: CollisionSide,
+    /// The signed displacement along the axis of smallest overlap needed to move `a` so it no
+    /// longer intersects `b`.
+    pub depth: Vec2,
+}
+
+/// Tests two axis-aligned boxes, given by their center position and full size, for overlap.
+///
+/// On overlap, resolves along the axis of *smallest* overlap, so a box resting in a corner
+/// resolves out through whichever face it barely clips rather than the face it's deeply
+/// embedded in.
+pub fn collide(a_pos: Vec2, a_size: Vec2, b_pos: Vec2, b_size: Vec2) -> Option<Collision> {
+    let a_half = a_size / 2.0;
+    let b_half = b_size / 2.0;
+
+    let a_min = a_pos - a_half;
+    let a_max = a_pos + a_half;
+    let b_min = b_pos - b_half;
+    let b_max = b_pos + b_half;
+
+    if a_min.x >= b_max.x || a_max.x <= b_min.x || a_min.y >= b_max.y || a_max.y <= b_min.y {
+        return None;
+    }
+
+    let overlap_x = a_max.x.min(b_max.x) - a_min.x.max(b_min.x);
+    let overlap_y = a_max.y.min(b_max.y) - a_min.y.max(b_min.y);
+
+    let towards_negative_x = a_pos.x < b_pos.x;
+    let towards_negative_y = a_pos.y < b_pos.y;
+
+    if overlap_x < overlap_y {
+        let side = if towards_negative_x {
+            CollisionSide::Left
+        } else {
+            CollisionSide::Right
+        };
+        let depth = Vec2::new(if towards_negative_x { -overlap_x } else { overlap_x }, 0.0);
+
+        Some(Collision { side, depth })
+    } else {
+        let side = if towards_negative_y {
+            CollisionSide::Top
+        } else {
+            CollisionSide::Bottom
+        };
+        let depth = Vec2::new(0.0, if towards_negative_y { -overlap_y } else { overlap_y });
+
+        Some(Collision { side, depth })
+    }
+}
+
+/// Pushes `position` out of the collider by `collision`'s penetration depth, then reflects
+/// `velocity` off the struck axis if it was moving into the collider.
+///
+/// Resolving the penetration before reflecting fixes the classic "stuck inside the wall" bug a
+/// bounding-volume-only check suffers from, where a deeply overlapping body keeps re-triggering
+/// the same collision every frame.
+pub fn resolve(position: &mut Vec2, velocity: &mut Vec2, collision: Collision) {
+    *position += collision.depth;
+
+    let moving_into_collider = match collision.side {
+        CollisionSide::Left => velocity.x > 0.0,
+        CollisionSide::Right => velocity.x < 0.0,
+        CollisionSide::Top => velocity.y > 0.0,
+        CollisionSide::Bottom => velocity.y < 0.0,
+    };
+
+    if !moving_into_collider {
+        return;
+    }
+
+    match collision.side {
+        CollisionSide::Left | CollisionSide::Right => velocity.x = -velocity.x,
+        CollisionSide::Top | CollisionSide::Bottom => velocity.y = -velocity.y,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn no_overlap_returns_none() {
+        let a = Vec2::new(0.0, 0.0);
+        let b = Vec2::new(100.0, 100.0);
+
+        assert_eq!(collide(a, Vec2::splat(10.0), b, Vec2::splat(10.0)), None);
+    }
+
+    #[test]
+    fn corner_overlap_resolves_along_smallest_axis() {
+        // `a` barely clips `b`'s left edge (overlap_x == 2.0) while fully overlapping on y
+        // (overlap_y == 10.0), so resolution should push out along x, not y.
+        let a_pos = Vec2::new(-8.0, 0.0);
+        let b_pos = Vec2::ZERO;
+
+        let collision = collide(a_pos, Vec2::new(10.0, 10.0), b_pos, Vec2::new(10.0, 10.0))
+            .expect("boxes overlap");
+
+        assert_eq!(collision.side, CollisionSide::Left);
+        assert_eq!(collision.depth, Vec2::new(-2.0, 0.0));
+    }
+
+    #[test]
+    fn resolve_pushes_out_and_reflects_when_moving_into_collider() {
+        let mut position = Vec2::new(5.0, 0.0);
+        let mut velocity = Vec2::new(-1.0, 0.0);
+        let collision = Collision {
+            side: CollisionSide::Right,
+            depth: Vec2::new(3.0, 0.0),
+        };
+
+        resolve(&mut position, &mut velocity, collision);
+
+        assert_eq!(position, Vec2::new(8.0, 0.0));
+        assert_eq!(velocity, Vec2::new(1.0, 0.0));
+    }
+
+    #[test]
+    fn resolve_does_not_reflect_when_already_moving_away() {
+        let mut position = Vec2::new(5.0, 0.0);
+        let mut velocity = Vec2::new(1.0, 0.0);
+        let collision = Collision {
+            side: CollisionSide::Right,
+            depth: Vec2::new(3.0, 0.0),
+        };
+
+        resolve(&mut position, &mut velocity, collision);
+
+        // Still pushed out of the collider, but velocity is left alone since the body was
+        // already moving away from the struck side.
+        assert_eq!(position, Vec2::new(8.0, 0.0));
+        assert_eq!(velocity, Vec2::new(1.0, 0.0));
+    }
+}
@@ -0,0 +1,400 @@
+//! Deterministic fixed-step rollback networking for two-player link-cable multiplayer, modeled
+//! on GGRS.
+//!
+//! Gameplay that must stay in sync goes into [`RollbackSchedule`] instead of `Update`. Each real
+//! tick, [`RollbackPlugin`] samples the local player's input, exchanges it with the remote player
+//! over a [`RollbackTransport`], and runs [`RollbackSchedule`] once. If the remote input for an
+//! earlier frame arrives and turns out to differ from what was predicted, the world is restored
+//! from the last confirmed [`RollbackComponent`] snapshot and re-simulated forward with the
+//! corrected input before resuming.
+
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+use core::mem::size_of;
+
+use bevy::ecs::schedule::ScheduleLabel;
+use bevy::prelude::*;
+use bytemuck::Pod;
+
+/// How many frames of input and world-state history the rollback ring buffer retains.
+///
+/// This bounds both the maximum rollback depth and the size of the snapshot ring buffer, which
+/// matters on hardware as RAM-constrained as the GBA.
+pub const MAX_ROLLBACK_FRAMES: usize = 8;
+
+/// The dedicated fixed-timestep schedule rollback-critical gameplay systems run in, instead of
+/// `Update`, so [`RollbackPlugin`] can re-run them during resimulation without also re-running
+/// unrelated systems.
+#[derive(ScheduleLabel, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct RollbackSchedule;
+
+/// A small, plain-old-data input sampled once per rollback frame and exchanged with the remote
+/// player, e.g. a packed set of button bits.
+///
+/// Kept as small as possible, since every unconfirmed frame holds a copy of it in the ring
+/// buffer.
+pub trait RollbackInput: Pod + Default + PartialEq + Send + Sync {}
+
+impl<I: Pod + Default + PartialEq + Send + Sync> RollbackInput for I {}
+
+/// The local player's input for the current rollback frame.
+///
+/// A game should update this resource (from gamepad state, say) once per [`RollbackSchedule`]
+/// tick; [`RollbackPlugin`] reads it at the start of the following tick.
+#[derive(Resource, Clone, Copy, Default)]
+pub struct LocalInput<I: RollbackInput>(pub I);
+
+/// The authoritative input for the rollback frame currently being simulated.
+///
+/// Set by [`rollback_tick`] immediately before every [`RollbackSchedule`] run, including each
+/// pass of a resimulation -- gameplay systems should read this instead of [`LocalInput`], since
+/// during a replay it carries the corrected input for that frame rather than whatever is live
+/// right now.
+#[derive(Resource, Clone, Copy, Default)]
+pub struct CurrentFrameInput<I: RollbackInput> {
+    /// The local player's input for this frame.
+    pub local: I,
+    /// The remote player's input for this frame: confirmed if it has arrived by now, otherwise
+    /// the same prediction that was simulated the first time this frame ran.
+    pub remote: I,
+}
+
+/// Abstracts how rollback input reaches the remote player, so a session isn't tied to `agb`'s
+/// serial link hardware outside of actual GBA hardware.
+pub trait RollbackTransport<I: RollbackInput>: Send + Sync + 'static {
+    /// Sends the local player's `input`, sampled for `frame`, to the remote player.
+    fn send(&mut self, frame: u32, input: I);
+
+    /// Drains every remote input confirmed since the last call, oldest first.
+    fn receive(&mut self) -> Vec<(u32, I)>;
+}
+
+/// The default [`RollbackTransport`], exchanging inputs with the remote GBA over the serial link
+/// cable.
+pub struct SerialLinkTransport {
+    port: agb::sio::SerialIo,
+}
+
+impl SerialLinkTransport {
+    /// Takes ownership of the console's serial port for the duration of the rollback session.
+    pub fn new(port: agb::sio::SerialIo) -> Self {
+        Self { port }
+    }
+}
+
+impl<I: RollbackInput> RollbackTransport<I> for SerialLinkTransport {
+    fn send(&mut self, frame: u32, input: I) {
+        let mut packet = Vec::with_capacity(4 + size_of::<I>());
+        packet.extend_from_slice(&frame.to_le_bytes());
+        packet.extend_from_slice(bytemuck::bytes_of(&input));
+
+        self.port.transfer(&packet);
+    }
+
+    fn receive(&mut self) -> Vec<(u32, I)> {
+        let mut confirmed = Vec::new();
+
+        while let Some(packet) = self.port.try_receive() {
+            let frame = u32::from_le_bytes(packet[..4].try_into().unwrap());
+            let input = *bytemuck::from_bytes::<I>(&packet[4..4 + size_of::<I>()]);
+
+            confirmed.push((frame, input));
+        }
+
+        confirmed
+    }
+}
+
+/// A component whose value is captured in rollback snapshots.
+///
+/// Any `Component` that is also [`Pod`] qualifies automatically -- there is nothing to implement
+/// by hand. Register each type that needs to roll back with
+/// [`RollbackAppExt::register_rollback_component`].
+pub trait RollbackComponent: Component + Pod {}
+
+impl<T: Component + Pod> RollbackComponent for T {}
+
+type SnapshotFn = fn(&mut World, &mut Vec<u8>);
+type RestoreFn = fn(&mut World, &mut &[u8]);
+
+/// The set of component types a rollback session snapshots and restores, populated by
+/// [`RollbackAppExt::register_rollback_component`].
+#[derive(Resource, Default, Clone)]
+struct RollbackRegistry {
+    entries: Vec<(SnapshotFn, RestoreFn)>,
+}
+
+fn snapshot_component<T: RollbackComponent>(world: &mut World, out: &mut Vec<u8>) {
+    let mut query = world.query::<&T>();
+    let values: Vec<&T> = query.iter(world).collect();
+
+    out.extend_from_slice(&(values.len() as u32).to_le_bytes());
+
+    for value in values {
+        out.extend_from_slice(bytemuck::bytes_of(value));
+    }
+}
+
+fn restore_component<T: RollbackComponent>(world: &mut World, bytes: &mut &[u8]) {
+    let (count_bytes, rest) = bytes.split_at(4);
+    let count = u32::from_le_bytes(count_bytes.try_into().unwrap());
+    *bytes = rest;
+
+    let mut query = world.query::<&mut T>();
+    let mut entities = query.iter_mut(world);
+
+    for _ in 0..count {
+        let size = size_of::<T>();
+        let (value_bytes, rest) = bytes.split_at(size);
+        *bytes = rest;
+
+        // Assumes the complement of rollback entities doesn't change between snapshot and
+        // restore, which holds for the fixed set of actors typical of these games.
+        if let Some(mut component) = entities.next() {
+            *component = *bytemuck::from_bytes::<T>(value_bytes);
+        }
+    }
+}
+
+/// Extension trait for registering the component types a [`RollbackPlugin`] session snapshots.
+pub trait RollbackAppExt {
+    /// Registers `T` to be captured and restored by rollback snapshots.
+    ///
+    /// This is this crate's equivalent of a `#[derive(Rollback)]` -- since snapshotting is
+    /// just a byte-for-byte copy, there's no behavior to implement per type, only registration.
+    fn register_rollback_component<T: RollbackComponent>(&mut self) -> &mut Self;
+}
+
+impl RollbackAppExt for App {
+    fn register_rollback_component<T: RollbackComponent>(&mut self) -> &mut Self {
+        self.world_mut()
+            .get_resource_or_insert_with(RollbackRegistry::default)
+            .entries
+            .push((snapshot_component::<T>, restore_component::<T>));
+
+        self
+    }
+}
+
+#[derive(Clone, Copy)]
+struct FrameRecord<I: RollbackInput> {
+    frame: u32,
+    local: I,
+    predicted_remote: I,
+    confirmed_remote: Option<I>,
+}
+
+/// Tracks in-flight input history and the world-state ring buffer for a rollback session.
+#[derive(Resource)]
+struct RollbackState<I: RollbackInput> {
+    frame: u32,
+    records: [Option<FrameRecord<I>>; MAX_ROLLBACK_FRAMES],
+    snapshots: [Option<(u32, Vec<u8>)>; MAX_ROLLBACK_FRAMES],
+}
+
+impl<I: RollbackInput> Default for RollbackState<I> {
+    fn default() -> Self {
+        Self {
+            frame: 0,
+            records: [None; MAX_ROLLBACK_FRAMES],
+            snapshots: [const { None }; MAX_ROLLBACK_FRAMES],
+        }
+    }
+}
+
+impl<I: RollbackInput> RollbackState<I> {
+    fn slot(frame: u32) -> usize {
+        frame as usize % MAX_ROLLBACK_FRAMES
+    }
+
+    /// The record stored for `frame`, or `None` if that slot has since been overwritten by a
+    /// newer frame -- the ring buffer only holds `MAX_ROLLBACK_FRAMES` of history.
+    fn record_for(&self, frame: u32) -> Option<FrameRecord<I>> {
+        match self.records[Self::slot(frame)] {
+            Some(record) if record.frame == frame => Some(record),
+            _ => None,
+        }
+    }
+
+    fn record(&mut self, frame: u32, local: I) {
+        let predicted_remote = self
+            .record_for(frame.wrapping_sub(1))
+            .map(|previous| previous.confirmed_remote.unwrap_or(previous.predicted_remote))
+            .unwrap_or_default();
+
+        self.records[Self::slot(frame)] = Some(FrameRecord {
+            frame,
+            local,
+            predicted_remote,
+            confirmed_remote: None,
+        });
+    }
+
+    /// Confirms the remote player's real input for `frame`, returning `true` if it differs from
+    /// the prediction that was simulated at the time.
+    ///
+    /// Returns `false` without effect if `frame` has already fallen out of the ring buffer's
+    /// history, so a confirmation that arrives too late can't be mistaken for a newer frame's.
+    fn confirm_remote(&mut self, frame: u32, input: I) -> bool {
+        let Some(record) = self.records[Self::slot(frame)].as_mut() else {
+            return false;
+        };
+
+        if record.frame != frame {
+            return false;
+        }
+
+        let mispredicted = record.predicted_remote != input;
+        record.confirmed_remote = Some(input);
+
+        mispredicted
+    }
+
+    fn store_snapshot(&mut self, frame: u32, bytes: Vec<u8>) {
+        self.snapshots[Self::slot(frame)] = Some((frame, bytes));
+    }
+
+    fn snapshot(&self, frame: u32) -> Option<&[u8]> {
+        match &self.snapshots[Self::slot(frame)] {
+            Some((snapshot_frame, bytes)) if *snapshot_frame == frame => Some(bytes),
+            _ => None,
+        }
+    }
+}
+
+/// Adds a deterministic, rollback-capable fixed-step session driving [`RollbackSchedule`] at
+/// [`RollbackPlugin::TICK_RATE`] Hz.
+///
+/// Gameplay systems go in [`RollbackSchedule`] and should read [`CurrentFrameInput`] rather than
+/// [`LocalInput`], since it reflects whatever frame is currently being simulated -- including
+/// during a replay. State that must survive a rollback is registered with
+/// [`RollbackAppExt::register_rollback_component`]; the local player's input is sampled into
+/// [`LocalInput`] once per tick and exchanged over a [`RollbackTransport`] inserted as a
+/// `NonSend` resource (see [`SerialLinkTransport`] for the default `agb` implementation).
+pub struct RollbackPlugin<I: RollbackInput> {
+    _marker: PhantomData<fn() -> I>,
+}
+
+impl<I: RollbackInput> Default for RollbackPlugin<I> {
+    fn default() -> Self {
+        Self {
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<I: RollbackInput> RollbackPlugin<I> {
+    /// The fixed tick rate the rollback session runs at, matching the GBA's screen refresh rate.
+    pub const TICK_RATE: f64 = 60.0;
+}
+
+impl<I: RollbackInput> Plugin for RollbackPlugin<I> {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(Time::<Fixed>::from_hz(Self::TICK_RATE))
+            .init_resource::<RollbackRegistry>()
+            .init_resource::<RollbackState<I>>()
+            .init_resource::<LocalInput<I>>()
+            .init_resource::<CurrentFrameInput<I>>()
+            .add_systems(FixedUpdate, rollback_tick::<I>);
+    }
+}
+
+fn rollback_tick<I: RollbackInput>(world: &mut World) {
+    let frame = world.resource::<RollbackState<I>>().frame;
+    let local = world.resource::<LocalInput<I>>().0;
+
+    world
+        .resource_mut::<RollbackState<I>>()
+        .record(frame, local);
+
+    // Without a transport there's no remote player to reconcile against -- run the frame
+    // straight through rather than leaving `RollbackSchedule` frozen forever.
+    let mut transport = world.remove_non_send_resource::<Box<dyn RollbackTransport<I>>>();
+
+    let confirmed = if let Some(transport) = transport.as_mut() {
+        transport.send(frame, local);
+        transport.receive()
+    } else {
+        Vec::new()
+    };
+
+    if let Some(transport) = transport {
+        world.insert_non_send_resource(transport);
+    }
+
+    let mut rollback_to = None;
+
+    for (remote_frame, remote_input) in confirmed {
+        let mispredicted = world
+            .resource_mut::<RollbackState<I>>()
+            .confirm_remote(remote_frame, remote_input);
+
+        if mispredicted {
+            rollback_to = Some(match rollback_to {
+                Some(earliest) if earliest <= remote_frame => earliest,
+                _ => remote_frame,
+            });
+        }
+    }
+
+    if let Some(rollback_frame) = rollback_to {
+        let registry = world.resource::<RollbackRegistry>().clone();
+        // `snapshot(frame)` holds the state *after* `frame` ran, so the state entering
+        // `rollback_frame` -- the baseline the replay below must start from -- is the
+        // snapshot tagged with the preceding frame.
+        let mut bytes = world
+            .resource::<RollbackState<I>>()
+            .snapshot(rollback_frame.wrapping_sub(1))
+            .map(<[u8]>::to_vec);
+
+        if let Some(bytes) = bytes.take() {
+            let mut cursor = bytes.as_slice();
+
+            for (_, restore) in &registry.entries {
+                restore(world, &mut cursor);
+            }
+        }
+
+        for replay_frame in rollback_frame..=frame {
+            set_current_input::<I>(world, replay_frame);
+            world.run_schedule(RollbackSchedule);
+            snapshot_frame::<I>(world, &registry, replay_frame);
+        }
+    } else {
+        let registry = world.resource::<RollbackRegistry>().clone();
+
+        set_current_input::<I>(world, frame);
+        world.run_schedule(RollbackSchedule);
+        snapshot_frame::<I>(world, &registry, frame);
+    }
+
+    world.resource_mut::<RollbackState<I>>().frame = frame + 1;
+}
+
+/// Populates [`CurrentFrameInput`] from the stored [`FrameRecord`] for `frame`, so the upcoming
+/// [`RollbackSchedule`] run -- whether the original tick or a replay pass -- sees the right input.
+fn set_current_input<I: RollbackInput>(world: &mut World, frame: u32) {
+    let record = world.resource::<RollbackState<I>>().record_for(frame);
+
+    let current = record
+        .map(|record| CurrentFrameInput {
+            local: record.local,
+            remote: record.confirmed_remote.unwrap_or(record.predicted_remote),
+        })
+        .unwrap_or_default();
+
+    world.insert_resource(current);
+}
+
+fn snapshot_frame<I: RollbackInput>(world: &mut World, registry: &RollbackRegistry, frame: u32) {
+    let mut bytes = Vec::new();
+
+    for (snapshot, _) in &registry.entries {
+        snapshot(world, &mut bytes);
+    }
+
+    world
+        .resource_mut::<RollbackState<I>>()
+        .store_snapshot(frame, bytes);
+}
@@ -0,0 +1,178 @@
+//! Tile-based scrolling backgrounds rendered through the GBA's hardware background layers.
+
+use alloc::vec::Vec;
+
+use agb::display::tiled::TileSetting;
+use bevy::prelude::*;
+
+use crate::video::Video;
+
+/// The number of hardware background layers the GBA exposes.
+const HARDWARE_BACKGROUND_LAYERS: usize = 4;
+
+/// The size, in pixels, of a single GBA background tile.
+const TILE_SIZE: i32 = 8;
+
+/// Adds the tile-background subsystem: uploading [`Tilemap`] changes to VRAM and scrolling the
+/// map behind whichever entity carries [`ScrollTarget`].
+#[derive(Default, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct TilemapPlugin;
+
+impl Plugin for TilemapPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<BackgroundLayer>()
+            .add_systems(Update, (upload_dirty_tiles, scroll_tilemaps).chain());
+    }
+}
+
+/// A set of 8x8 tile graphics uploaded to VRAM, shared by any number of [`Tilemap`]s.
+#[derive(Clone, Copy)]
+pub struct Tileset(pub &'static agb::display::tiled::TileSet<'static>);
+
+/// A 2D grid of tile indices backed by a [`Tileset`], rendered onto one of the GBA's four
+/// hardware background layers by [`TilemapPlugin`].
+#[derive(Component, Clone)]
+pub struct Tilemap {
+    tileset: Tileset,
+    width: u32,
+    height: u32,
+    tiles: Vec<TileSetting>,
+    dirty: bool,
+}
+
+impl Tilemap {
+    /// Creates a `width` by `height` map, filled with `fill`, backed by `tileset`.
+    pub fn new(tileset: Tileset, width: u32, height: u32, fill: TileSetting) -> Self {
+        Self {
+            tileset,
+            width,
+            height,
+            tiles: alloc::vec![fill; (width * height) as usize],
+            dirty: true,
+        }
+    }
+
+    /// The map's width, in tiles.
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// The map's height, in tiles.
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// The tile at `(x, y)`, or `None` if out of bounds.
+    pub fn get(&self, x: u32, y: u32) -> Option<TileSetting> {
+        self.tiles.get((y * self.width + x) as usize).copied()
+    }
+
+    /// Sets the tile at `(x, y)`, marking the map for re-upload to VRAM.
+    ///
+    /// Does nothing if `(x, y)` is out of bounds.
+    pub fn set(&mut self, x: u32, y: u32, tile: TileSetting) {
+        if let Some(slot) = self.tiles.get_mut((y * self.width + x) as usize) {
+            *slot = tile;
+            self.dirty = true;
+        }
+    }
+}
+
+/// Marks the [`Transform`] that scrolling [`Tilemap`]s should follow, such as the player or camera.
+#[derive(Component, Clone, Copy, Debug, Default)]
+pub struct ScrollTarget;
+
+/// Tracks which of the GBA's four hardware background layers each [`Tilemap`] entity has been
+/// assigned, since the hardware only offers `HARDWARE_BACKGROUND_LAYERS` of them.
+#[derive(Resource, Default)]
+pub struct BackgroundLayer {
+    layers: [Option<Entity>; HARDWARE_BACKGROUND_LAYERS],
+}
+
+impl BackgroundLayer {
+    /// The hardware layer index assigned to `entity`, if any.
+    pub fn layer_of(&self, entity: Entity) -> Option<usize> {
+        self.layers.iter().position(|layer| *layer == Some(entity))
+    }
+
+    /// Assigns `entity` the first free hardware layer, if one is available.
+    fn assign(&mut self, entity: Entity) -> Option<usize> {
+        let slot = self.layers.iter().position(Option::is_none)?;
+        self.layers[slot] = Some(entity);
+        Some(slot)
+    }
+}
+
+/// Uploads every [`Tilemap`] that has changed since it was last rendered to VRAM.
+fn upload_dirty_tiles(
+    mut video: ResMut<Video>,
+    mut layers: ResMut<BackgroundLayer>,
+    mut tilemaps: Query<(Entity, &mut Tilemap)>,
+) {
+    let (_background, mut vram) = video.tiled0();
+
+    for (entity, mut tilemap) in &mut tilemaps {
+        if !tilemap.dirty {
+            continue;
+        }
+
+        let Some(layer) = layers
+            .layer_of(entity)
+            .or_else(|| layers.assign(entity))
+        else {
+            continue;
+        };
+
+        for y in 0..tilemap.height {
+            for x in 0..tilemap.width {
+                if let Some(tile) = tilemap.get(x, y) {
+                    vram.set_tile(layer, x, y, tilemap.tileset.0, tile);
+                }
+            }
+        }
+
+        tilemap.dirty = false;
+    }
+}
+
+/// Scrolls every [`Tilemap`] so that [`ScrollTarget`] stays centered, without ever exposing the
+/// edge of the map.
+fn scroll_tilemaps(
+    mut video: ResMut<Video>,
+    layers: Res<BackgroundLayer>,
+    target: Option<Single<&Transform, With<ScrollTarget>>>,
+    tilemaps: Query<(Entity, &Tilemap)>,
+) {
+    const SCREEN_WIDTH: i32 = 240;
+    const SCREEN_HEIGHT: i32 = 160;
+
+    let Some(target) = target else {
+        return;
+    };
+
+    let (_background, mut vram) = video.tiled0();
+
+    for (entity, tilemap) in &tilemaps {
+        let Some(layer) = layers.layer_of(entity) else {
+            continue;
+        };
+
+        let map_width = tilemap.width as i32 * TILE_SIZE;
+        let map_height = tilemap.height as i32 * TILE_SIZE;
+
+        let scroll_x = clamp_scroll(target.translation.x as i32, map_width, SCREEN_WIDTH);
+        let scroll_y = clamp_scroll(target.translation.y as i32, map_height, SCREEN_HEIGHT);
+
+        vram.set_scroll(layer, scroll_x, scroll_y);
+    }
+}
+
+/// Clamps a camera's scroll offset so the map edge never shows, centering the map when it is
+/// narrower than the screen.
+fn clamp_scroll(target: i32, map_size: i32, screen_size: i32) -> i32 {
+    if map_size <= screen_size {
+        return -(screen_size - map_size) / 2;
+    }
+
+    (target - screen_size / 2).clamp(0, map_size - screen_size)
+}